@@ -1,4 +1,4 @@
-use crate::{ Frame, Time };
+use crate::{ Frame, Time, Input };
 
 /// represents an application that can be run by the framework
 pub trait App: 'static
@@ -17,4 +17,8 @@ pub trait App: 'static
 
     /// update the state of the app
     fn update(&mut self, time: &Time);
+
+    /// react to an input event (key, mouse motion, scroll). the default
+    /// implementation ignores all input
+    fn input(&mut self, _event: Input) {}
 }
\ No newline at end of file
@@ -0,0 +1,62 @@
+use crate::math::*;
+
+/// a perspective camera, producing the view and projection matrices the
+/// shader pipeline composes into its model-view-projection uniform
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Camera
+{
+    /// position of the camera, in world space
+    pub eye: Vec3<f32>,
+    /// point the camera is looking at
+    pub target: Vec3<f32>,
+    /// world up direction
+    pub up: Vec3<f32>,
+
+    /// vertical field of view, in radians
+    pub fov: f32,
+    /// viewport aspect ratio (width / height)
+    pub aspect: f32,
+    /// near clipping plane distance
+    pub near: f32,
+    /// far clipping plane distance
+    pub far: f32,
+}
+
+impl Camera
+{
+    /// the view matrix, transforming world space into camera space
+    pub fn view(&self) -> Mat4<f32>
+    {
+        Mat4::look_at_rh(self.eye, self.target, self.up)
+    }
+
+    /// the perspective projection matrix
+    pub fn projection(&self) -> Mat4<f32>
+    {
+        Mat4::perspective_rh_no(self.fov, self.aspect, self.near, self.far)
+    }
+
+    /// the composed view-projection matrix
+    pub fn matrix(&self) -> Mat4<f32>
+    {
+        self.projection() * self.view()
+    }
+}
+
+impl Default for Camera
+{
+    fn default() -> Self
+    {
+        Self
+        {
+            eye: Vec3::new(0.0, 0.0, 3.0),
+            target: Vec3::zero(),
+            up: Vec3::unit_y(),
+
+            fov: std::f32::consts::FRAC_PI_3,
+            aspect: 1.0,
+            near: 0.1,
+            far: 100.0,
+        }
+    }
+}
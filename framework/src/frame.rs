@@ -123,6 +123,45 @@ impl<'a> Frame<'a>
     }
 }
 
+/// a non-overlapping, scanline-aligned sub-rectangle of a [Frame],
+/// handed out by [Frame::par_tiles_mut] so worker threads can rasterize
+/// their own region without any locking on the color or depth buffers
+#[derive(Debug)]
+pub struct Tile<'a>
+{
+    /// this tile's pixel bytes, row-major and full-width
+    pub pixels: &'a mut [u8],
+    /// y coordinate of the tile's top row within the frame
+    pub y: usize,
+    /// width of the frame, in pixels (a tile spans the full width)
+    pub width: usize,
+    /// number of rows in this tile
+    pub height: usize,
+}
+
+impl<'a> Frame<'a>
+{
+    /// split the framebuffer into horizontal tiles of at most `tile_h`
+    /// rows each, yielded as disjoint mutable [Tile]s for parallel
+    /// rasterization
+    pub fn par_tiles_mut(&mut self, tile_h: usize) -> impl IndexedParallelIterator<Item = Tile<'_>>
+    {
+        let w = self.width;
+        let stride = w * 4;
+
+        self.inner
+            .par_chunks_mut(stride * tile_h)
+            .enumerate()
+            .map(move |(i, pixels)| Tile
+            {
+                height: pixels.len() / stride,
+                y: i * tile_h,
+                width: w,
+                pixels,
+            })
+    }
+}
+
 impl<'a> Bitmap for Frame<'a>
 {
     /// get this framebuffer's width, in pixels
@@ -0,0 +1,36 @@
+use crate::math::*;
+
+/// a single input event delivered to [App::input]
+///
+/// [App::input]: crate::App::input
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Input
+{
+    /// a key was pressed or released
+    Key { key: Key, pressed: bool },
+    /// the mouse moved by the given pixel delta
+    Mouse { delta: Vec2<f32> },
+    /// the scroll wheel moved by the given amount
+    Scroll { delta: f32 },
+}
+
+/// the subset of keyboard keys the framework reports; mirrors the
+/// controls used by the orbit/fly camera examples
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Key
+{
+    Left,
+    Right,
+    Up,
+    Down,
+
+    W,
+    A,
+    S,
+    D,
+    Q,
+    E,
+
+    Space,
+    Shift,
+}
@@ -1,10 +1,14 @@
 mod frame;
 mod time;
 mod app;
+mod camera;
+mod input;
 
 pub use frame::Frame;
 pub use time::Time;
 pub use app::App;
+pub use camera::Camera;
+pub use input::{ Input, Key };
 
 /// run the app, hyjacking the main thread until the
 /// window is closed
@@ -87,6 +91,9 @@ pub fn run_instance<T: App>(mut state: T)
                 pixels.resize(size.width, size.height);
             }
 
+            // forward input events to the app
+            dispatch_input(&input, &mut state);
+
             // update state
             state.update(time.update());
             
@@ -94,4 +101,50 @@ pub fn run_instance<T: App>(mut state: T)
             window.request_redraw();
         }
     });
+}
+
+/// translate the polled winit input state into [Input] events and
+/// forward each one to the app
+fn dispatch_input<T: App>(input: &winit_input_helper::WinitInputHelper, state: &mut T)
+{
+    use winit::event::VirtualKeyCode as Vk;
+
+    use crate::math::*;
+    use crate::{ Input, Key };
+
+    // keys the framework reports, paired with their winit code
+    const KEYS: &[(Vk, Key)] =
+    &[
+        (Vk::Left, Key::Left), (Vk::Right, Key::Right),
+        (Vk::Up, Key::Up), (Vk::Down, Key::Down),
+        (Vk::W, Key::W), (Vk::A, Key::A), (Vk::S, Key::S), (Vk::D, Key::D),
+        (Vk::Q, Key::Q), (Vk::E, Key::E),
+        (Vk::Space, Key::Space), (Vk::LShift, Key::Shift),
+    ];
+
+    for &(vk, key) in KEYS
+    {
+        if input.key_pressed(vk)
+        {
+            state.input(Input::Key { key, pressed: true });
+        }
+        if input.key_released(vk)
+        {
+            state.input(Input::Key { key, pressed: false });
+        }
+    }
+
+    // relative mouse motion
+    let (dx, dy) = input.mouse_diff();
+    if dx != 0.0 || dy != 0.0
+    {
+        state.input(Input::Mouse { delta: Vec2::new(dx, dy) });
+    }
+
+    // scroll wheel
+    let scroll = input.scroll_diff();
+    if scroll != 0.0
+    {
+        state.input(Input::Scroll { delta: scroll });
+    }
 }
\ No newline at end of file
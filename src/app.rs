@@ -2,76 +2,68 @@ use framework::math::*;
 use framework::*;
 
 use crate::obj::Obj;
-use crate::draw;
+use crate::render::{ Renderer, Rasterizer };
 
 pub struct MyApp
 {
-    light: Vec3<f32>,
+    /// orbit camera viewing the model
+    cam: Camera,
+
+    /// model yaw/pitch, driven by the arrow keys
+    yaw: f32,
+    pitch: f32,
+    /// uniform model scale, driven by the scroll wheel
+    scale: f32,
 
-    tex: Image,
     obj: Obj,
 }
 
 impl App for MyApp
 {
-    fn render(&mut self, frame: &mut Frame)
+    fn render(&self, frame: &mut Frame)
     {
-        // reset frame
-        frame.clear(Rgba::black());
-        
-        // reset the Z buffer
-        let mut depth = [f32::MIN; Self::SIZE.w * Self::SIZE.h];
-
-        // frame size as a float vector
-        let size: Vec2<f32> = Self::SIZE.as_().into();
-
-        // lighting
-        let light_dir = self.light.normalized();
-
-        // iterate object's triangle faces
-        for [mut v0, mut v1, mut v2] in self.obj.iter_faces()
+        // model transform from the interactive state
+        let model = Mat4::<f32>::scaling_3d(self.scale)
+            * Mat4::rotation_y(self.yaw)
+            * Mat4::rotation_x(self.pitch);
+
+        // drive the frame through the rasterizing Renderer backend, whose
+        // composed MVP comes from the interactive camera
+        let mut renderer = Rasterizer
         {
-            // transformation matrix
-            let t = Mat3::identity()
-                .scaled_3d([150.0, -150.0, 150.0]);
-
-            // reframe
-            v0.pos = v0.pos * t + size / 2.0;
-            v1.pos = v1.pos * t + size / 2.0;
-            v2.pos = v2.pos * t + size / 2.0;
-
-            // lighting
-            let n = (v2.pos - v0.pos).cross(v1.pos - v0.pos).normalized();
-            let l = (n.dot(light_dir)).clamped_minus1_1().powi(2);
-
-            // visible face
-            if l > 0.0
-            {
-                // lighting color
-                let col = Rgb::broadcast((l * 255.0) as u8).into();
-
-                // draw mesh
-                draw::triangle(frame, &mut depth, [v0, v1, v2], col);
-
-                // // prepare wireframe
-                // let pts = [v0.xy().as_(), v1.xy().as_(), v2.xy().as_()];
-                // let wht = Rgba::white();
+            mvp: self.cam.matrix() * model,
+            model,
+            light: (self.cam.eye - self.cam.target).normalized(),
+            view: self.cam.eye,
+        };
 
-                // // draw wireframe
-                // draw::line(frame, pts[0], pts[1], wht);
-                // draw::line(frame, pts[0], pts[2], wht);
-                // draw::line(frame, pts[1], pts[2], wht);
-            }
-        }
+        renderer.render(frame, &self.obj);
     }
 
     fn update(&mut self, time: &Time)
     {
         println!("FPS: {:.1}", 1.0 / time.dt());
+    }
 
-        let (s, c) = time.elapsed().as_secs_f32().sin_cos();
+    fn input(&mut self, event: Input)
+    {
+        // step sizes for a keypress/scroll notch
+        const TURN: f32 = 0.1;
+        const ZOOM: f32 = 0.1;
 
-        self.light = Vec3::new(c, s, c * s);
+        match event
+        {
+            Input::Key { key, pressed: true } => match key
+            {
+                Key::Left => self.yaw -= TURN,
+                Key::Right => self.yaw += TURN,
+                Key::Up => self.pitch -= TURN,
+                Key::Down => self.pitch += TURN,
+                _ => {}
+            },
+            Input::Scroll { delta } => self.scale = (self.scale + delta * ZOOM).max(0.01),
+            _ => {}
+        }
     }
 }
 
@@ -81,10 +73,17 @@ impl Default for MyApp
     {
         Self
         {
-            light: Vec3::zero(),
+            cam: Camera
+            {
+                aspect: Self::WIDTH as f32 / Self::HEIGHT as f32,
+                ..Camera::default()
+            },
 
-            tex: Image::open("res/head_diffuse.tga").unwrap(),
-            obj: Obj::open("res/head.obj"),
+            yaw: 0.0,
+            pitch: 0.0,
+            scale: 1.0,
+
+            obj: Obj::load("res/head.obj"),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,11 @@
+use rayon::prelude::*;
+
 use framework::util::{ Bresenham, Triangle };
 use framework::math::*;
 use framework::*;
 
 use crate::obj::Vertex;
+use crate::shader::{ Shader, Varying };
 
 /// draws a line on top of the given bitmap. pixels out of bound
 /// will be clipped
@@ -18,32 +21,176 @@ pub fn line(frame: &mut Frame, a: Vec2<i32>, b: Vec2<i32>, col: Rgba<u8>)
     }
 }
 
-/// draw a triangle on top of the given bitmap. pixels out of
-/// bound will be clipped
-pub fn triangle(frame: &mut Frame, depth: &mut [f32], tex: &Image, tri: [Vertex; 3], col: Rgba<u8>)
+/// height, in rows, of a single rasterization tile
+const TILE_HEIGHT: usize = 16;
+
+/// rasterize a stream of triangle faces through a programmable [Shader].
+/// every face is run through `S::vertex` to yield a clip-space position
+/// and a set of `Varyings`; those are near-plane clipped, perspective-
+/// divided and viewport-mapped, then `S::fragment` is evaluated at every
+/// covered pixel with the barycentric-interpolated varyings and written
+/// behind a depth test. callers with per-mesh uniforms (e.g. one
+/// [Material] each) iterate their meshes and feed [Mesh::iter_faces]
+/// directly, so no throwaway [Obj] is ever allocated.
+///
+/// [Shader]: crate::shader::Shader
+/// [Material]: crate::obj::Material
+/// [Obj]: crate::obj::Obj
+/// [Mesh::iter_faces]: crate::obj::Mesh::iter_faces
+pub fn shade_faces<S, I>(frame: &mut Frame, depth: &mut [f32], faces: I, uniforms: &S::Uniforms)
+where
+    S: Shader<Vertex = Vertex>,
+    S::Uniforms: Sync,
+    S::Varyings: Varying + Copy + Send + Sync,
+    I: Iterator<Item = [Vertex; 3]>,
 {
-    // convert
-    let max = frame.size().as_();
-    let pts = [tri[0].pos.xy().as_(), tri[1].pos.xy().as_(), tri[2].pos.xy().as_()];
+    // frame size as a float vector, for the viewport transform
+    let size: Vec2<f32> = frame.size().as_().into();
+    // integer bounds for the triangle tracer
+    let max = frame.size().as_::<i32>();
+    let width = frame.width();
+
+    // run the geometry stage up front: vertex shade + near-plane clip +
+    // fan triangulation, yielding screen-space triangles to be binned
+    let mut tris: Vec<ScreenTri<S::Varyings>> = Vec::new();
+    for face in faces
+    {
+        let verts = face.map(|v| S::vertex(v, uniforms));
+        let poly = clip_near(&verts);
+
+        for i in 1..poly.len().saturating_sub(1)
+        {
+            tris.push(project::<S>([poly[0], poly[i], poly[i + 1]], size));
+        }
+    }
+
+    // rasterize tiles in parallel; each worker owns its tile's slice of
+    // the color and depth buffers, so no locking is needed
+    frame
+        .par_tiles_mut(TILE_HEIGHT)
+        .zip(depth.par_chunks_mut(width * TILE_HEIGHT))
+        .for_each(|(tile, dep)|
+        {
+            let y0 = tile.y as i32;
+            let y1 = y0 + tile.height as i32;
+
+            // bin: only triangles whose bounding box reaches this tile
+            for tri in tris.iter().filter(|t| t.ymax >= y0 && t.ymin < y1)
+            {
+                for (pt, br) in Triangle::new_bounded(tri.scr, max)
+                {
+                    // clip to this tile's rows
+                    if pt.y < y0 || pt.y >= y1
+                    {
+                        continue;
+                    }
+                    // tile-local buffer index
+                    let local = (pt.y - y0) as usize * width + pt.x as usize;
+
+                    // interpolated depth, affine in screen space
+                    let pt_z = f32::interpolate(tri.dep, br);
+                    let bf_z = &mut dep[local];
+
+                    if pt_z < *bf_z
+                    {
+                        *bf_z = pt_z;
+
+                        // perspective-correct the varyings, then shade
+                        let frag = S::fragment(S::Varyings::interpolate_persp(tri.var, tri.inv_w, br), uniforms);
+                        let bytes: [u8; 4] = frag.into();
+                        let i = local * 4;
+
+                        tile.pixels[i..i + 4].copy_from_slice(&bytes);
+                    }
+                }
+            }
+        });
+}
 
-    for (pt, br) in Triangle::new_bounded(pts, max)
+/// a triangle that has cleared clipping and been projected to screen
+/// space, carrying everything the per-tile rasterizer needs
+struct ScreenTri<V>
+{
+    /// screen-space pixel positions
+    scr: [Vec2<i32>; 3],
+    /// per-vertex depth (affine in screen space)
+    dep: Vec3<f32>,
+    /// per-vertex 1/w, for perspective-correct interpolation
+    inv_w: Vec3<f32>,
+    /// per-vertex varyings
+    var: Vec3<V>,
+
+    /// vertical bounds, used to bin the triangle into tiles
+    ymin: i32,
+    ymax: i32,
+}
+
+/// perspective-divide and viewport-map a clipped triangle, packaging it
+/// for the tiled rasterizer
+fn project<S>(tri: [(Vec4<f32>, S::Varyings); 3], size: Vec2<f32>) -> ScreenTri<S::Varyings>
+where
+    S: Shader<Vertex = Vertex>,
+    S::Varyings: Varying + Copy,
+{
+    // clip-space positions and their matching varyings
+    let clip = [tri[0].0, tri[1].0, tri[2].0];
+    let var = Vec3::new(tri[0].1, tri[1].1, tri[2].1);
+
+    // perspective divide into NDC, then map to screen space
+    let scr = clip.map(|pos|
+    {
+        let ndc = pos.xyz() / pos.w;
+
+        Vec2::new((ndc.x + 1.0) * 0.5 * size.x, (1.0 - ndc.y) * 0.5 * size.y)
+            .as_::<i32>()
+    });
+    let dep = Vec3::new(clip[0].z / clip[0].w, clip[1].z / clip[1].w, clip[2].z / clip[2].w);
+    let inv_w = Vec3::new(1.0 / clip[0].w, 1.0 / clip[1].w, 1.0 / clip[2].w);
+
+    let ymin = scr[0].y.min(scr[1].y).min(scr[2].y);
+    let ymax = scr[0].y.max(scr[1].y).max(scr[2].y);
+
+    ScreenTri { scr, dep, inv_w, var, ymin, ymax }
+}
+
+/// vertices at or behind `w = NEAR_EPSILON` would divide by a
+/// non-positive `w`; anything closer than this to the camera is culled
+const NEAR_EPSILON: f32 = 1.0e-5;
+
+/// clip a convex clip-space polygon against the `w > epsilon` near plane
+/// using the Sutherland–Hodgman algorithm, interpolating both position
+/// and every varying across edges that straddle the plane. the result is
+/// a convex polygon of 3..=4 vertices (or empty if fully culled).
+fn clip_near<V>(poly: &[(Vec4<f32>, V)]) -> Vec<(Vec4<f32>, V)>
+where
+    V: Varying + Copy,
+{
+    let mut out = Vec::with_capacity(poly.len() + 1);
+
+    for i in 0..poly.len()
     {
-        // triangle point depth
-        let pt_z = tri[0].pos.z * br.x + tri[1].pos.z * br.y + tri[2].pos.z * br.z;
-        // depth buffer z
-        let bf_z = &mut depth[pt.y as usize * frame.width() + pt.x as usize];
+        let (pa, va) = poly[i];
+        let (pb, vb) = poly[(i + 1) % poly.len()];
+
+        // signed distance to the near plane for each endpoint
+        let da = pa.w - NEAR_EPSILON;
+        let db = pb.w - NEAR_EPSILON;
 
-        // depth comparison
-        if *bf_z < pt_z
+        // keep the starting vertex if it is inside
+        if da >= 0.0
+        {
+            out.push((pa, va));
+        }
+        // the edge crosses the plane: emit the intersection vertex
+        if (da >= 0.0) != (db >= 0.0)
         {
-            *bf_z = pt_z;
+            let t = da / (da - db);
 
-            // triangle UV
-            let u = tri[0].tex.x * br.x + tri[1].tex.x * br.y + tri[2].tex.x * br.z;
-            let v = tri[0].tex.y * br.y + tri[1].tex.y * br.y + tri[2].tex.y * br.z;
+            let pos = pa + (pb - pa) * t;
+            let var = V::interpolate(Vec3::new(va, vb, va), Vec3::new(1.0 - t, t, 0.0));
 
-            // draw triangle
-            frame.set(pt, col);
+            out.push((pos, var));
         }
     }
+    out
 }
\ No newline at end of file
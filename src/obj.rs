@@ -6,7 +6,10 @@ use framework::math::*;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Obj
 {
-    pub meshes: Vec<Mesh>
+    pub meshes: Vec<Mesh>,
+    /// materials parsed from the companion `.mtl` file, indexed by
+    /// [Mesh::material]
+    pub materials: Vec<Material>,
 }
 
 /// represents a mesh within a wavefront [Obj]
@@ -20,8 +23,98 @@ pub struct Mesh
 
     /// arbitrarily sorted list of vertices
     pub verts: Vec<Vertex>,
-    /// list of triangular faces
-    pub inds: Vec<[usize; 3]>,
+    /// list of faces, each an arbitrary-length polygon (`k >= 3`) of
+    /// vertex indices; [Mesh::iter_faces] fan-triangulates them
+    pub inds: Vec<Vec<usize>>,
+    /// index into [Obj::materials] of the material applied to this mesh
+    /// (from its `usemtl` statement), if any
+    pub material: Option<usize>,
+}
+
+/// a surface material parsed from a wavefront `.mtl` file
+///
+/// [Obj]: self::Obj
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material
+{
+    /// this material's name (`newmtl`)
+    pub name: String,
+
+    /// ambient reflectivity (`Ka`).
+    ///
+    /// note: the underlying parser reports an absent statement as `0 0 0`,
+    /// indistinguishable from an explicit black. a fully-black value is
+    /// therefore replaced with a neutral default on load — see
+    /// [Material::from] — so a legitimately black `Ka` cannot be expressed.
+    pub ambient: Rgb<f32>,
+    /// diffuse reflectivity (`Kd`). shares the all-zero fallback described
+    /// on [Material::ambient].
+    pub diffuse: Rgb<f32>,
+    /// specular reflectivity (`Ks`). shares the all-zero fallback described
+    /// on [Material::ambient].
+    pub specular: Rgb<f32>,
+    /// emissive color (`Ke`)
+    pub emissive: Rgb<f32>,
+    /// specular exponent (`Ns`)
+    pub shininess: f32,
+    /// illumination model (`illum`)
+    pub illum: u8,
+
+    /// path to the diffuse texture map (`map_Kd`), relative to the
+    /// `.mtl` file
+    pub diffuse_map: Option<String>,
+}
+
+impl From<tobj::Material> for Material
+{
+    fn from(m: tobj::Material) -> Self
+    {
+        /// extract an `Rgb` from a raw `.mtl` triplet, falling back to
+        /// `default` when the statement was absent, so a material without
+        /// e.g. `Kd` isn't rendered black.
+        ///
+        /// tobj reports an absent statement as a zeroed slice with no
+        /// presence flag, so "absent" and an explicit `0 0 0` can't be
+        /// told apart; this is the lossy heuristic documented on the
+        /// `Material` color fields.
+        fn color([r, g, b]: [f32; 3], default: Rgb<f32>) -> Rgb<f32>
+        {
+            if r == 0.0 && g == 0.0 && b == 0.0
+            {
+                default
+            }
+            else
+            {
+                Rgb::new(r, g, b)
+            }
+        }
+        /// `Ke` isn't a first-class field, so fish it out of the
+        /// unrecognized statements
+        fn emissive(m: &tobj::Material) -> Rgb<f32>
+        {
+            m.unknown_param
+                .get("Ke")
+                .and_then(|s|
+                {
+                    let mut it = s.split_whitespace().filter_map(|n| n.parse().ok());
+
+                    Some(Rgb::new(it.next()?, it.next()?, it.next()?))
+                })
+                .unwrap_or_else(Rgb::zero)
+        }
+
+        Self
+        {
+            ambient: color(m.ambient, Rgb::broadcast(0.05)),
+            diffuse: color(m.diffuse, Rgb::broadcast(0.8)),
+            specular: color(m.specular, Rgb::broadcast(0.2)),
+            emissive: emissive(&m),
+            shininess: m.shininess,
+            illum: m.illumination_model.unwrap_or(0),
+            diffuse_map: if m.diffuse_texture.is_empty() { None } else { Some(m.diffuse_texture) },
+            name: m.name,
+        }
+    }
 }
 
 /// represents a vertex within a [Mesh]
@@ -41,21 +134,23 @@ pub struct Vertex
 impl Obj
 {
     /// read a new wavefront object from its path. assumes the vertices
-    /// have all position, normal, and texture coordinates. all faces must
-    /// be triangular
+    /// have all position, normal, and texture coordinates. faces may be
+    /// arbitrary polygons, and are fan-triangulated on iteration
     pub fn load(path: &str) -> Self
     {
-        // load .obj
-        let (models, _) = tobj::load_obj(path, true).unwrap();
+        // load .obj along with its companion .mtl materials, preserving
+        // n-gon faces so iteration can triangulate them itself
+        let (models, materials) = tobj::load_obj(path, false).unwrap();
+
+        // map materials into our own representation
+        let materials = materials
+            .into_iter()
+            .map(Material::from)
+            .collect();
 
         // go through every mesh
         let meshes = models.into_iter().map(|tobj::Model { name, mesh }|
         {
-            /// maps a slice(assumed length 3) to an array of usize
-            fn map_indices(i: &[u32]) -> [usize; 3]
-            {
-                [i[0] as usize, i[1] as usize, i[2] as usize]
-            }
             /// maps position, normal, and texture coordinate slices to
             /// a vertex
             fn map_vertex(((pos, nor), tex): ((&[f32], &[f32]), &[f32])) -> Vertex
@@ -68,11 +163,17 @@ impl Obj
                 }
             }
 
-            // map indices
-            let inds: Vec<[usize; 3]> = mesh.indices
-                .chunks_exact(3)
-                .map(map_indices)
-                .collect();
+            // map faces, slicing the flat index list into polygons of
+            // `num_face_indices` vertices each
+            let mut inds: Vec<Vec<usize>> = Vec::with_capacity(mesh.num_face_indices.len());
+            let mut off = 0;
+            for &count in &mesh.num_face_indices
+            {
+                let count = count as usize;
+
+                inds.push(mesh.indices[off..off + count].iter().map(|&i| i as usize).collect());
+                off += count;
+            }
             // map vertices
             let verts: Vec<Vertex> = mesh.positions
                 .chunks_exact(3)
@@ -81,12 +182,12 @@ impl Obj
                 .map(map_vertex)
                 .collect();
 
-            // build the mesh
-            Mesh { name, verts, inds }
+            // build the mesh, keeping its `usemtl` association
+            Mesh { name, verts, inds, material: mesh.material_id }
 
         }).collect();
 
-        Self { meshes }
+        Self { meshes, materials }
     }
 
     /// iterate all the faces in all the meshes in this wavefront scene
@@ -100,11 +201,19 @@ impl Obj
 
 impl Mesh
 {
-    /// iterate this mesh's faces
+    /// iterate this mesh's faces as triangles. faces with more than three
+    /// vertices are fan-triangulated `(v0, v_i, v_{i+1})`, which is exact
+    /// for convex polygons, so downstream code only ever sees triangles
     pub fn iter_faces(&self) -> impl Iterator<Item = [Vertex; 3]> + '_
     {
         self.inds
             .iter()
-            .map(move |[a, b, c]| [self.verts[*a], self.verts[*b], self.verts[*c]])
+            .flat_map(move |face|
+            {
+                (1..face.len().saturating_sub(1)).map(move |i|
+                {
+                    [self.verts[face[0]], self.verts[face[i]], self.verts[face[i + 1]]]
+                })
+            })
     }
 }
\ No newline at end of file
@@ -0,0 +1,474 @@
+use framework::math::*;
+use framework::*;
+
+/// the winding rule used to decide which areas of a [Path] are filled
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FillRule
+{
+    /// a point is inside if the signed crossing count is non-zero
+    NonZero,
+    /// a point is inside if the crossing count is odd
+    EvenOdd,
+}
+
+/// how a [Path] is stroked: line width plus an optional dash pattern of
+/// alternating on/off lengths
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke
+{
+    /// total width of the stroke, centered on the path
+    pub width: f32,
+    /// alternating on/off dash lengths; empty for a solid stroke
+    pub dashes: Vec<f32>,
+}
+
+impl Default for Stroke
+{
+    fn default() -> Self
+    {
+        Self { width: 1.0, dashes: Vec::new() }
+    }
+}
+
+/// a 2D vector path built from move/line/quadratic/cubic segments. béziers
+/// are flattened to line segments on demand, and the resulting polygons
+/// can be filled (with a winding rule) or stroked, both with analytic
+/// per-scanline anti-aliasing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path
+{
+    verbs: Vec<Verb>,
+}
+
+/// a single path-building command
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Verb
+{
+    Move(Vec2<f32>),
+    Line(Vec2<f32>),
+    Quad(Vec2<f32>, Vec2<f32>),
+    Cubic(Vec2<f32>, Vec2<f32>, Vec2<f32>),
+    Close,
+}
+
+/// maximum deviation, in pixels, allowed when flattening a bézier
+const FLATTEN_TOLERANCE: f32 = 0.1;
+
+impl Path
+{
+    /// start a new, empty path
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// begin a new subpath at `p`
+    pub fn move_to(&mut self, p: impl Into<Vec2<f32>>) -> &mut Self
+    {
+        self.verbs.push(Verb::Move(p.into()));
+        self
+    }
+
+    /// add a straight line from the current point to `p`
+    pub fn line_to(&mut self, p: impl Into<Vec2<f32>>) -> &mut Self
+    {
+        self.verbs.push(Verb::Line(p.into()));
+        self
+    }
+
+    /// add a quadratic bézier with control point `c` ending at `p`
+    pub fn quad_to(&mut self, c: impl Into<Vec2<f32>>, p: impl Into<Vec2<f32>>) -> &mut Self
+    {
+        self.verbs.push(Verb::Quad(c.into(), p.into()));
+        self
+    }
+
+    /// add a cubic bézier with control points `c0`/`c1` ending at `p`
+    pub fn cubic_to(&mut self, c0: impl Into<Vec2<f32>>, c1: impl Into<Vec2<f32>>, p: impl Into<Vec2<f32>>) -> &mut Self
+    {
+        self.verbs.push(Verb::Cubic(c0.into(), c1.into(), p.into()));
+        self
+    }
+
+    /// close the current subpath back to its starting point
+    pub fn close(&mut self) -> &mut Self
+    {
+        self.verbs.push(Verb::Close);
+        self
+    }
+
+    /// flatten this path into a list of polylines (one per subpath),
+    /// tessellating any béziers to within [FLATTEN_TOLERANCE]
+    fn flatten(&self) -> Vec<Vec<Vec2<f32>>>
+    {
+        let mut subs: Vec<Vec<Vec2<f32>>> = Vec::new();
+        let mut cur: Vec<Vec2<f32>> = Vec::new();
+        let mut start = Vec2::zero();
+
+        for verb in &self.verbs
+        {
+            match *verb
+            {
+                Verb::Move(p) =>
+                {
+                    if cur.len() > 1 { subs.push(std::mem::take(&mut cur)); }
+                    cur.clear();
+                    cur.push(p);
+                    start = p;
+                }
+                Verb::Line(p) => cur.push(p),
+                Verb::Quad(c, p) =>
+                {
+                    let a = *cur.last().unwrap_or(&start);
+                    flatten_quad(a, c, p, &mut cur);
+                }
+                Verb::Cubic(c0, c1, p) =>
+                {
+                    let a = *cur.last().unwrap_or(&start);
+                    flatten_cubic(a, c0, c1, p, &mut cur);
+                }
+                Verb::Close =>
+                {
+                    cur.push(start);
+                    if cur.len() > 1 { subs.push(std::mem::take(&mut cur)); }
+                    cur.clear();
+                }
+            }
+        }
+        if cur.len() > 1 { subs.push(cur); }
+        subs
+    }
+
+    /// fill this path onto `frame` with `col`, using the given winding
+    /// rule and analytic anti-aliasing
+    pub fn fill(&self, frame: &mut Frame, col: Rgba<u8>, rule: FillRule)
+    {
+        let mut raster = Raster::new(frame.width(), frame.height());
+
+        for poly in self.flatten()
+        {
+            raster.add_polygon(&poly);
+        }
+        raster.composite(frame, col, rule);
+    }
+
+    /// stroke this path onto `frame` with `col`, honoring the given
+    /// [Stroke] width and dash pattern
+    pub fn stroke(&self, frame: &mut Frame, col: Rgba<u8>, style: &Stroke)
+    {
+        let mut raster = Raster::new(frame.width(), frame.height());
+        let half = style.width * 0.5;
+
+        for poly in self.flatten()
+        {
+            // split the polyline into dash segments, then thicken each
+            // into a quad the accumulator can fill
+            for seg in dash(&poly, &style.dashes)
+            {
+                for w in seg.windows(2)
+                {
+                    for quad in thicken(w[0], w[1], half)
+                    {
+                        raster.add_polygon(&quad);
+                    }
+                }
+            }
+        }
+        // self-overlapping quads make nonzero the natural choice here
+        raster.composite(frame, col, FillRule::NonZero);
+    }
+}
+
+/// recursively flatten a quadratic bézier, pushing points onto `out`
+fn flatten_quad(a: Vec2<f32>, c: Vec2<f32>, b: Vec2<f32>, out: &mut Vec<Vec2<f32>>)
+{
+    // distance of the control point from the chord estimates flatness
+    let d = ((c - a) + (c - b)).magnitude();
+
+    if d <= FLATTEN_TOLERANCE
+    {
+        out.push(b);
+        return;
+    }
+    let ab = (a + c) * 0.5;
+    let cb = (c + b) * 0.5;
+    let mid = (ab + cb) * 0.5;
+
+    flatten_quad(a, ab, mid, out);
+    flatten_quad(mid, cb, b, out);
+}
+
+/// recursively flatten a cubic bézier, pushing points onto `out`
+fn flatten_cubic(a: Vec2<f32>, c0: Vec2<f32>, c1: Vec2<f32>, b: Vec2<f32>, out: &mut Vec<Vec2<f32>>)
+{
+    // deviation of the control points from the chord estimates flatness
+    let d = (c0 * 3.0 - a * 2.0 - b).magnitude()
+        .max((c1 * 3.0 - a - b * 2.0).magnitude());
+
+    if d <= FLATTEN_TOLERANCE
+    {
+        out.push(b);
+        return;
+    }
+    let ab = (a + c0) * 0.5;
+    let bc = (c0 + c1) * 0.5;
+    let cd = (c1 + b) * 0.5;
+    let abc = (ab + bc) * 0.5;
+    let bcd = (bc + cd) * 0.5;
+    let mid = (abc + bcd) * 0.5;
+
+    flatten_cubic(a, ab, abc, mid, out);
+    flatten_cubic(mid, bcd, cd, b, out);
+}
+
+/// thicken a single segment into a pair of triangles (a quad) covering
+/// the stroke's width
+fn thicken(a: Vec2<f32>, b: Vec2<f32>, half: f32) -> [Vec<Vec2<f32>>; 2]
+{
+    let dir = (b - a).try_normalized().unwrap_or_else(Vec2::zero);
+    let n = Vec2::new(-dir.y, dir.x) * half;
+
+    let p0 = a + n;
+    let p1 = b + n;
+    let p2 = b - n;
+    let p3 = a - n;
+
+    [vec![p0, p1, p2, p0], vec![p0, p2, p3, p0]]
+}
+
+/// split a polyline into sub-polylines according to a dash pattern; an
+/// empty pattern yields the polyline unchanged
+fn dash(poly: &[Vec2<f32>], pattern: &[f32]) -> Vec<Vec<Vec2<f32>>>
+{
+    if pattern.is_empty() || !pattern.iter().any(|&d| d > 0.0)
+    {
+        // an empty pattern, or one with no drawable length, leaves the
+        // polyline solid rather than spinning on a zero-length dash
+        return vec![poly.to_vec()];
+    }
+
+    let mut out = Vec::new();
+    let mut cur = Vec::new();
+
+    // index into the dash pattern and remaining length of that dash.
+    // zero- or negative-length entries flip the on/off phase without
+    // consuming any length, so skip past them to land on a positive dash
+    let mut di = 0;
+    let mut on = true;
+    while pattern[di] <= 0.0
+    {
+        di = (di + 1) % pattern.len();
+        on = !on;
+    }
+    let mut rem = pattern[di];
+
+    for w in poly.windows(2)
+    {
+        let (mut a, b) = (w[0], w[1]);
+        let mut len = (b - a).magnitude();
+        let dir = (b - a).try_normalized().unwrap_or_else(Vec2::zero);
+
+        if on && cur.is_empty() { cur.push(a); }
+
+        while len > rem
+        {
+            // advance to the end of the current dash
+            a = a + dir * rem;
+            if on
+            {
+                cur.push(a);
+                out.push(std::mem::take(&mut cur));
+            }
+            else
+            {
+                cur.clear();
+                cur.push(a);
+            }
+            len -= rem;
+
+            // move to the next drawable dash, flipping the phase across
+            // any zero-length entries so `rem` stays positive
+            loop
+            {
+                di = (di + 1) % pattern.len();
+                on = !on;
+                if pattern[di] > 0.0 { break; }
+            }
+            rem = pattern[di];
+        }
+        rem -= len;
+        if on { cur.push(b); }
+    }
+    if cur.len() > 1 { out.push(cur); }
+    out
+}
+
+/// a signed-area coverage accumulator. each edge deposits fractional
+/// coverage deltas per scanline; a prefix sum across each row recovers
+/// per-pixel coverage without any inside/outside test.
+struct Raster
+{
+    w: usize,
+    h: usize,
+    /// one signed-area cell per pixel, plus two guard columns per row
+    a: Vec<f32>,
+}
+
+impl Raster
+{
+    fn new(w: usize, h: usize) -> Self
+    {
+        // two guard columns: an edge clamped to the right border gives
+        // x0i == w and writes through row + w + 1, so the row needs w + 2 cells
+        Self { w, h, a: vec![0.0; (w + 2) * h] }
+    }
+
+    /// accumulate a closed polygon, treating each consecutive pair of
+    /// points as an edge and implicitly closing the ring
+    fn add_polygon(&mut self, poly: &[Vec2<f32>])
+    {
+        if poly.len() < 2
+        {
+            return;
+        }
+        for w in poly.windows(2)
+        {
+            self.add_edge(w[0], w[1]);
+        }
+        // implicit closing edge
+        self.add_edge(poly[poly.len() - 1], poly[0]);
+    }
+
+    /// deposit the signed-area contribution of a single edge. ported from
+    /// the font-rs scanline coverage method.
+    fn add_edge(&mut self, p0: Vec2<f32>, p1: Vec2<f32>)
+    {
+        // orient the edge downwards, remembering its winding direction
+        let (dir, p0, p1) = if p0.y < p1.y { (1.0, p0, p1) } else { (-1.0, p1, p0) };
+
+        if p0.y == p1.y
+        {
+            return;
+        }
+        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+
+        // clip vertically to the raster
+        let mut x = p0.x;
+        let y_top = p0.y.max(0.0);
+        if p0.y < 0.0
+        {
+            x += (y_top - p0.y) * dxdy;
+        }
+        let y_bot = p1.y.min(self.h as f32);
+
+        let mut y = y_top.floor() as usize;
+        while (y as f32) < y_bot
+        {
+            let row = y * (self.w + 2);
+
+            // vertical slice of the edge within this scanline
+            let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
+            let xnext = x + dxdy * dy;
+            let d = dy * dir;
+
+            // horizontal span touched this scanline
+            let (x0, x1) = if x < xnext { (x, xnext) } else { (xnext, x) };
+            let x0 = x0.max(0.0).min(self.w as f32);
+            let x1 = x1.max(0.0).min(self.w as f32);
+
+            let x0f = x0.floor();
+            let x0i = x0f as usize;
+            let x1i = x1.ceil() as usize;
+
+            if x1i <= x0i + 1
+            {
+                // span falls inside a single pixel: split coverage by the
+                // midpoint's fractional x
+                let xmf = (0.5 * (x0 + x1) - x0f).max(0.0).min(1.0);
+
+                self.a[row + x0i] += d * (1.0 - xmf);
+                self.a[row + x0i + 1] += d * xmf;
+            }
+            else
+            {
+                // span crosses several pixels: trapezoidal coverage
+                let inv = 1.0 / (x1 - x0);
+                let x0frac = x0 - x0f;
+                let a0 = 0.5 * inv * (1.0 - x0frac) * (1.0 - x0frac);
+                let x1frac = x1 - (x1i as f32) + 1.0;
+                let an = 0.5 * inv * x1frac * x1frac;
+
+                self.a[row + x0i] += d * a0;
+                if x1i == x0i + 2
+                {
+                    self.a[row + x0i + 1] += d * (1.0 - a0 - an);
+                }
+                else
+                {
+                    let a1 = inv * (1.5 - x0frac);
+                    self.a[row + x0i + 1] += d * (a1 - a0);
+
+                    for xi in x0i + 2..x1i - 1
+                    {
+                        self.a[row + xi] += d * inv;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * inv;
+                    self.a[row + x1i - 1] += d * (1.0 - a2 - an);
+                }
+                self.a[row + x1i] += d * an;
+            }
+            x = xnext;
+            y += 1;
+        }
+    }
+
+    /// resolve accumulated coverage and composite `col` over `frame`
+    fn composite(&self, frame: &mut Frame, col: Rgba<u8>, rule: FillRule)
+    {
+        let w = self.w;
+        let buf = frame.pixels_mut();
+
+        for y in 0..self.h
+        {
+            let row = y * (w + 2);
+
+            // running signed area; resets each row
+            let mut acc = 0.0;
+
+            for x in 0..w
+            {
+                acc += self.a[row + x];
+
+                // map the winding sum to [0, 1] coverage
+                let cov = match rule
+                {
+                    FillRule::NonZero => acc.abs().min(1.0),
+                    FillRule::EvenOdd =>
+                    {
+                        let m = acc.abs() % 2.0;
+                        if m > 1.0 { 2.0 - m } else { m }
+                    }
+                };
+
+                if cov <= 0.0
+                {
+                    continue;
+                }
+
+                // source-over composite of col (scaled by coverage)
+                let a = cov * (col.a as f32 / 255.0);
+                let i = (y * w + x) * 4;
+                let rgb = [col.r, col.g, col.b];
+
+                for c in 0..3
+                {
+                    let src = rgb[c] as f32;
+                    let dst = buf[i + c] as f32;
+
+                    buf[i + c] = (src * a + dst * (1.0 - a)) as u8;
+                }
+                buf[i + 3] = ((a + (buf[i + 3] as f32 / 255.0) * (1.0 - a)) * 255.0) as u8;
+            }
+        }
+    }
+}
@@ -0,0 +1,572 @@
+use framework::math::*;
+use framework::*;
+
+use crate::obj::{ Obj, Material };
+
+/// a backend capable of turning an [Obj] scene into pixels on a [Frame].
+/// the crate ships two implementations: the scanline [Rasterizer] and
+/// the [PathTracer] below, both consuming the same geometry/material
+/// data so an [App] can pick whichever it wants.
+///
+/// [Obj]: crate::obj::Obj
+/// [App]: framework::App
+pub trait Renderer
+{
+    /// render the object into the frame, overwriting its contents
+    fn render(&mut self, frame: &mut Frame, obj: &Obj);
+}
+
+/// the classic scanline backend: runs the programmable pipeline from
+/// [crate::draw::shade_faces] with a Blinn–Phong shader per mesh
+pub struct Rasterizer
+{
+    /// combined model-view-projection matrix
+    pub mvp: Mat4<f32>,
+    /// model matrix, bringing geometry into world space
+    pub model: Mat4<f32>,
+    /// direction towards the light
+    pub light: Vec3<f32>,
+    /// camera position, in world space
+    pub view: Vec3<f32>,
+}
+
+impl Renderer for Rasterizer
+{
+    fn render(&mut self, frame: &mut Frame, obj: &Obj)
+    {
+        use crate::shader::{ PhongShader, PhongUniforms };
+
+        // reset frame and depth buffer
+        frame.clear(Rgba::black());
+        let mut depth = vec![f32::MAX; frame.width() * frame.height()];
+
+        // a neutral fallback for meshes that carry no material
+        let default = default_material();
+
+        // shade each mesh with its own material
+        for mesh in &obj.meshes
+        {
+            let material = mesh.material
+                .and_then(|i| obj.materials.get(i))
+                .cloned()
+                .unwrap_or_else(|| default.clone());
+
+            let uniforms = PhongUniforms
+            {
+                mvp: self.mvp,
+                model: self.model,
+                light: self.light,
+                view: self.view,
+                material,
+            };
+
+            // shade the mesh's faces in place; the shared depth buffer
+            // resolves occlusion across meshes
+            crate::draw::shade_faces::<PhongShader, _>(frame, &mut depth, mesh.iter_faces(), &uniforms);
+        }
+    }
+}
+
+/// a ray: an origin and a (unit) direction
+#[derive(Debug, Copy, Clone)]
+pub struct Ray
+{
+    pub origin: Vec3<f32>,
+    pub dir: Vec3<f32>,
+}
+
+/// a flattened triangle, ready for intersection
+#[derive(Debug, Copy, Clone)]
+struct Tri
+{
+    /// world-space positions
+    pos: [Vec3<f32>; 3],
+    /// world-space normals
+    nor: [Vec3<f32>; 3],
+    /// index into [Obj::materials]
+    material: Option<usize>,
+}
+
+impl Tri
+{
+    /// this triangle's axis-aligned bounding box
+    fn aabb(&self) -> Aabb
+    {
+        let mut b = Aabb::empty();
+
+        for p in &self.pos
+        {
+            b.expand(*p);
+        }
+        b
+    }
+
+    /// the centroid used to partition triangles during BVH construction
+    fn centroid(&self) -> Vec3<f32>
+    {
+        (self.pos[0] + self.pos[1] + self.pos[2]) / 3.0
+    }
+
+    /// Möller–Trumbore ray/triangle intersection; returns the hit
+    /// distance along the ray and the barycentric weights
+    fn intersect(&self, ray: &Ray) -> Option<(f32, Vec3<f32>)>
+    {
+        const EPSILON: f32 = 1.0e-6;
+
+        let e1 = self.pos[1] - self.pos[0];
+        let e2 = self.pos[2] - self.pos[0];
+
+        let pvec = ray.dir.cross(e2);
+        let det = e1.dot(pvec);
+
+        // ray is parallel to the triangle
+        if det.abs() < EPSILON
+        {
+            return None;
+        }
+
+        let inv = 1.0 / det;
+        let tvec = ray.origin - self.pos[0];
+
+        let u = tvec.dot(pvec) * inv;
+        if u < 0.0 || u > 1.0
+        {
+            return None;
+        }
+
+        let qvec = tvec.cross(e1);
+        let v = ray.dir.dot(qvec) * inv;
+        if v < 0.0 || u + v > 1.0
+        {
+            return None;
+        }
+
+        let t = e2.dot(qvec) * inv;
+        if t < EPSILON
+        {
+            return None;
+        }
+
+        Some((t, Vec3::new(1.0 - u - v, u, v)))
+    }
+}
+
+/// an axis-aligned bounding box
+#[derive(Debug, Copy, Clone)]
+struct Aabb
+{
+    min: Vec3<f32>,
+    max: Vec3<f32>,
+}
+
+impl Aabb
+{
+    /// an inside-out box that grows to fit whatever it [Aabb::expand]s over
+    fn empty() -> Self
+    {
+        Self { min: Vec3::broadcast(f32::MAX), max: Vec3::broadcast(f32::MIN) }
+    }
+
+    /// grow this box to contain a point
+    fn expand(&mut self, p: Vec3<f32>)
+    {
+        self.min = Vec3::partial_min(self.min, p);
+        self.max = Vec3::partial_max(self.max, p);
+    }
+
+    /// grow this box to contain another box
+    fn union(&mut self, other: &Aabb)
+    {
+        self.expand(other.min);
+        self.expand(other.max);
+    }
+
+    /// slab-based ray/box intersection, returning the entry distance if
+    /// the ray hits the box within `[0, limit]`
+    fn intersect(&self, ray: &Ray, inv_dir: Vec3<f32>, limit: f32) -> Option<f32>
+    {
+        let t0 = (self.min - ray.origin) * inv_dir;
+        let t1 = (self.max - ray.origin) * inv_dir;
+
+        let tmin = Vec3::partial_min(t0, t1);
+        let tmax = Vec3::partial_max(t0, t1);
+
+        let near = tmin.x.max(tmin.y).max(tmin.z);
+        let far = tmax.x.min(tmax.y).min(tmax.z);
+
+        if near <= far && far >= 0.0 && near <= limit
+        {
+            Some(near.max(0.0))
+        }
+        else
+        {
+            None
+        }
+    }
+}
+
+/// a node in the bounding-volume hierarchy. leaves hold a slice of the
+/// reordered triangle array; branches point at two children.
+#[derive(Debug, Copy, Clone)]
+enum Node
+{
+    Leaf { aabb: Aabb, start: usize, len: usize },
+    Branch { aabb: Aabb, left: usize, right: usize },
+}
+
+impl Node
+{
+    fn aabb(&self) -> Aabb
+    {
+        match self
+        {
+            Node::Leaf { aabb, .. } => *aabb,
+            Node::Branch { aabb, .. } => *aabb,
+        }
+    }
+}
+
+/// a binary bounding-volume hierarchy over a set of triangles, built by
+/// recursively median-splitting centroids along the longest axis
+struct Bvh
+{
+    tris: Vec<Tri>,
+    nodes: Vec<Node>,
+}
+
+/// leaves hold at most this many triangles before we stop splitting
+const BVH_LEAF_SIZE: usize = 4;
+
+impl Bvh
+{
+    /// build a hierarchy over the given triangles
+    fn build(mut tris: Vec<Tri>) -> Self
+    {
+        let mut nodes = Vec::new();
+
+        if !tris.is_empty()
+        {
+            let len = tris.len();
+            Self::split(&mut tris, &mut nodes, 0, len);
+        }
+        Self { tris, nodes }
+    }
+
+    /// recursively partition `tris[start..start + len]`, appending nodes
+    /// and returning the index of the subtree's root
+    fn split(tris: &mut [Tri], nodes: &mut Vec<Node>, start: usize, len: usize) -> usize
+    {
+        // bounds of this range
+        let mut aabb = Aabb::empty();
+        for t in &tris[start..start + len]
+        {
+            aabb.union(&t.aabb());
+        }
+
+        // small enough: make a leaf
+        if len <= BVH_LEAF_SIZE
+        {
+            let i = nodes.len();
+            nodes.push(Node::Leaf { aabb, start, len });
+            return i;
+        }
+
+        // split along the longest axis of the centroid bounds
+        let mut cb = Aabb::empty();
+        for t in &tris[start..start + len]
+        {
+            cb.expand(t.centroid());
+        }
+        let extent = cb.max - cb.min;
+        let axis = if extent.x > extent.y && extent.x > extent.z { 0 }
+            else if extent.y > extent.z { 1 }
+            else { 2 };
+
+        // median split: partition around the middle centroid on `axis`
+        let mid = len / 2;
+        tris[start..start + len].sort_by(|a, b|
+        {
+            a.centroid()[axis]
+                .partial_cmp(&b.centroid()[axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // reserve this branch's slot, then recurse
+        let i = nodes.len();
+        nodes.push(Node::Leaf { aabb, start, len }); // placeholder
+
+        let left = Self::split(tris, nodes, start, mid);
+        let right = Self::split(tris, nodes, start + mid, len - mid);
+
+        nodes[i] = Node::Branch { aabb, left, right };
+        i
+    }
+
+    /// trace a ray through the hierarchy, returning the nearest hit as
+    /// `(distance, triangle, barycentric)`
+    fn trace(&self, ray: &Ray) -> Option<(f32, &Tri, Vec3<f32>)>
+    {
+        if self.nodes.is_empty()
+        {
+            return None;
+        }
+
+        let inv_dir = Vec3::new(1.0 / ray.dir.x, 1.0 / ray.dir.y, 1.0 / ray.dir.z);
+
+        let mut best: Option<(f32, &Tri, Vec3<f32>)> = None;
+        let mut dist = f32::MAX;
+
+        // explicit stack, descending near-child first and pruning boxes
+        // farther than the current best hit
+        let mut stack = vec![0usize];
+
+        while let Some(n) = stack.pop()
+        {
+            let node = &self.nodes[n];
+
+            // prune boxes we can't beat
+            if node.aabb().intersect(ray, inv_dir, dist).is_none()
+            {
+                continue;
+            }
+
+            match *node
+            {
+                Node::Leaf { start, len, .. } =>
+                {
+                    for t in &self.tris[start..start + len]
+                    {
+                        if let Some((hit, bar)) = t.intersect(ray)
+                        {
+                            if hit < dist
+                            {
+                                dist = hit;
+                                best = Some((hit, t, bar));
+                            }
+                        }
+                    }
+                }
+                Node::Branch { left, right, .. } =>
+                {
+                    // push the farther child first so the nearer is
+                    // popped (and tested) first
+                    let dl = self.nodes[left].aabb().intersect(ray, inv_dir, dist);
+                    let dr = self.nodes[right].aabb().intersect(ray, inv_dir, dist);
+
+                    match (dl, dr)
+                    {
+                        (Some(l), Some(r)) if l < r => { stack.push(right); stack.push(left); }
+                        (Some(_), Some(_)) => { stack.push(left); stack.push(right); }
+                        (Some(_), None) => stack.push(left),
+                        (None, Some(_)) => stack.push(right),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+/// a simple diffuse path tracer sharing the rasterizer's geometry. shoots
+/// `samples` cosine-weighted paths per pixel and averages them.
+pub struct PathTracer
+{
+    /// camera position, in world space
+    pub origin: Vec3<f32>,
+    /// camera forward direction (unit)
+    pub forward: Vec3<f32>,
+    /// camera up direction (unit)
+    pub up: Vec3<f32>,
+    /// vertical field of view, in radians
+    pub fov: f32,
+
+    /// number of primary rays averaged per pixel
+    pub samples: usize,
+    /// maximum number of bounces per path
+    pub bounces: usize,
+}
+
+impl Renderer for PathTracer
+{
+    fn render(&mut self, frame: &mut Frame, obj: &Obj)
+    {
+        // flatten the scene into a BVH of world-space triangles
+        let bvh = Bvh::build(flatten(obj));
+
+        // camera basis
+        let right = self.forward.cross(self.up).normalized();
+        let up = right.cross(self.forward).normalized();
+
+        let size: Vec2<f32> = frame.size().as_().into();
+        let aspect = size.x / size.y;
+        let scale = (self.fov * 0.5).tan();
+
+        let origin = self.origin;
+        let forward = self.forward;
+        let samples = self.samples.max(1);
+        let bounces = self.bounces;
+
+        // derive (x, y) from the flat index directly: the framework's
+        // iterator computes `y` as `i / height`, which is wrong for a
+        // non-square frame
+        let w = frame.width();
+        for (i, px) in frame.pixels_mut().chunks_exact_mut(4).enumerate()
+        {
+            let x = i % w;
+            let y = i / w;
+
+            // normalized device coordinates, centered per pixel
+            let ndc = Vec2::new(
+                (2.0 * (x as f32 + 0.5) / size.x - 1.0) * aspect * scale,
+                (1.0 - 2.0 * (y as f32 + 0.5) / size.y) * scale,
+            );
+
+            let dir = (forward + right * ndc.x + up * ndc.y).normalized();
+            let ray = Ray { origin, dir };
+
+            // average a handful of paths per pixel
+            let mut acc = Rgb::zero();
+            let mut rng = Rng::new(x as u32 * 9781 + y as u32 * 6271 + 1);
+
+            for _ in 0..samples
+            {
+                acc = acc + radiance(&bvh, &obj.materials, ray, bounces, &mut rng);
+            }
+            let col = (acc / samples as f32)
+                .map(|c| (c.min(1.0).max(0.0) * 255.0) as u8);
+
+            px.copy_from_slice(&[col.r, col.g, col.b, 0xff]);
+        }
+    }
+}
+
+/// flatten every mesh face of an [Obj] into world-space [Tri]s
+fn flatten(obj: &Obj) -> Vec<Tri>
+{
+    let mut out = Vec::new();
+
+    for mesh in &obj.meshes
+    {
+        for [a, b, c] in mesh.iter_faces()
+        {
+            out.push(Tri
+            {
+                pos: [a.pos, b.pos, c.pos],
+                nor: [a.nor, b.nor, c.nor],
+                material: mesh.material,
+            });
+        }
+    }
+    out
+}
+
+/// trace a single path and return its accumulated radiance
+fn radiance(bvh: &Bvh, materials: &[Material], mut ray: Ray, bounces: usize, rng: &mut Rng) -> Rgb<f32>
+{
+    // running throughput along the path
+    let mut throughput = Rgb::one();
+
+    for _ in 0..bounces
+    {
+        match bvh.trace(&ray)
+        {
+            Some((dist, tri, bar)) =>
+            {
+                // surface material
+                let albedo = tri.material
+                    .and_then(|i| materials.get(i))
+                    .map(|m| m.diffuse)
+                    .unwrap_or_else(|| Rgb::broadcast(0.8));
+
+                // interpolated shading normal, facing the ray
+                let mut n = (tri.nor[0] * bar.x + tri.nor[1] * bar.y + tri.nor[2] * bar.z).normalized();
+                if n.dot(ray.dir) > 0.0
+                {
+                    n = -n;
+                }
+
+                // next bounce: cosine-weighted hemisphere around the normal
+                let hit = ray.origin + ray.dir * dist;
+                let dir = cosine_hemisphere(n, rng);
+
+                throughput = throughput * albedo;
+                ray = Ray { origin: hit + n * 1.0e-4, dir };
+            }
+            // escaped the scene: shade with a simple sky gradient
+            None =>
+            {
+                let t = 0.5 * (ray.dir.y + 1.0);
+                let sky = Rgb::broadcast(1.0) * (1.0 - t) + Rgb::new(0.5, 0.7, 1.0) * t;
+
+                return throughput * sky;
+            }
+        }
+    }
+    // exceeded the bounce budget
+    Rgb::zero()
+}
+
+/// sample a cosine-weighted direction in the hemisphere around `n`
+fn cosine_hemisphere(n: Vec3<f32>, rng: &mut Rng) -> Vec3<f32>
+{
+    let u1 = rng.next_f32();
+    let u2 = rng.next_f32();
+
+    let r = u1.sqrt();
+    let theta = 2.0 * std::f32::consts::PI * u2;
+
+    let x = r * theta.cos();
+    let y = r * theta.sin();
+    let z = (1.0 - u1).max(0.0).sqrt();
+
+    // orthonormal basis around the normal
+    let a = if n.x.abs() > 0.9 { Vec3::unit_y() } else { Vec3::unit_x() };
+    let t = n.cross(a).normalized();
+    let b = n.cross(t);
+
+    (t * x + b * y + n * z).normalized()
+}
+
+/// a tiny deterministic xorshift PRNG, so paths don't depend on thread
+/// state and frames stay reproducible
+struct Rng(u32);
+
+impl Rng
+{
+    fn new(seed: u32) -> Self
+    {
+        Self(seed | 1)
+    }
+
+    fn next_u32(&mut self) -> u32
+    {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.0 = x;
+        x
+    }
+
+    fn next_f32(&mut self) -> f32
+    {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// a neutral gray material for geometry that carries none
+fn default_material() -> Material
+{
+    Material
+    {
+        name: String::from("<default>"),
+        ambient: Rgb::broadcast(0.05),
+        diffuse: Rgb::broadcast(0.8),
+        specular: Rgb::broadcast(0.2),
+        emissive: Rgb::zero(),
+        shininess: 32.0,
+        illum: 2,
+        diffuse_map: None,
+    }
+}
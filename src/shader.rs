@@ -1,7 +1,7 @@
 use framework::math::*;
 use framework::*;
 
-struct BasicShader;
+pub struct BasicShader;
 
 impl Shader for BasicShader
 {
@@ -12,14 +12,85 @@ impl Shader for BasicShader
     fn vertex(v: Self::Vertex, (mvp,): &Self::Uniforms) -> (Vec4<f32>, Self::Varyings)
     {
         let pos = Vec4::new(v.pos.x, v.pos.y, v.pos.z, 1.0);
-        let mvp = *mvp;
 
-        (pos * mvp,(v.pos.z,))
+        (*mvp * pos, (v.pos.z,))
     }
 
-    fn fragment(v: Self::Varyings, u: &Self::Uniforms) -> Rgba<u8>
+    fn fragment((depth,): Self::Varyings, _: &Self::Uniforms) -> Rgba<u8>
     {
-        todo!()
+        // flat grayscale keyed off the interpolated depth, so the
+        // finished stub at least shows the mesh's silhouette
+        let shade = (depth.max(0.0).min(1.0) * 255.0) as u8;
+
+        Rgb::broadcast(shade).into()
+    }
+}
+
+/// uniforms consumed by [PhongShader]: the transforms, the shaded
+/// material, and a single (directional) light plus the camera position
+pub struct PhongUniforms
+{
+    /// combined model-view-projection matrix
+    pub mvp: Mat4<f32>,
+    /// model matrix, used to bring normals and positions into world space
+    pub model: Mat4<f32>,
+    /// material whose coefficients drive the lighting
+    pub material: crate::obj::Material,
+    /// direction *towards* the light
+    pub light: Vec3<f32>,
+    /// camera position, in world space, for the view/half vectors
+    pub view: Vec3<f32>,
+}
+
+/// a ready-made Blinn–Phong shader driven by an [Obj]'s [Material].
+/// computes ambient + diffuse + specular from the interpolated,
+/// perspective-correct world normal and the view direction.
+///
+/// [Obj]: crate::obj::Obj
+/// [Material]: crate::obj::Material
+pub struct PhongShader;
+
+impl Shader for PhongShader
+{
+    type Vertex = crate::obj::Vertex;
+    type Uniforms = PhongUniforms;
+    type Varyings = (Vec3<f32>, Vec3<f32>); // (world normal, world position)
+
+    fn vertex(v: Self::Vertex, u: &Self::Uniforms) -> (Vec4<f32>, Self::Varyings)
+    {
+        let pos = Vec4::new(v.pos.x, v.pos.y, v.pos.z, 1.0);
+        let nor = Vec4::new(v.nor.x, v.nor.y, v.nor.z, 0.0);
+
+        // world-space position and normal for the fragment stage
+        let world = (u.model * pos).xyz();
+        let normal = (u.model * nor).xyz().normalized();
+
+        (u.mvp * pos, (normal, world))
+    }
+
+    fn fragment((normal, world): Self::Varyings, u: &Self::Uniforms) -> Rgba<u8>
+    {
+        let m = &u.material;
+
+        // surface basis vectors
+        let n = normal.normalized();
+        let l = u.light.normalized();
+        let view = (u.view - world).normalized();
+        let half = (l + view).normalized();
+
+        // Blinn–Phong terms
+        let diff = n.dot(l).max(0.0);
+        let spec = n.dot(half).max(0.0).powf(m.shininess.max(1.0));
+
+        // accumulate and tonemap to 8-bit
+        let col = m.ambient
+            + m.emissive
+            + m.diffuse * diff
+            + m.specular * spec;
+
+        Rgb::new(col.r, col.g, col.b)
+            .map(|c| (c.min(1.0).max(0.0) * 255.0) as u8)
+            .into()
     }
 }
 
@@ -42,19 +113,88 @@ pub trait Shader
     fn fragment(v: Self::Varyings, u: &Self::Uniforms) -> Rgba<u8>;
 }
 
-trait VertexShaderOutput
-{
-
-}
-
 /// represents a type that can be interpolated using barycentric
 /// coordinates. ie, a triangle with 3 vertice(one red, one blue,
 /// and one yellow) will need to interpolate these colors for any
 /// point within its area
-trait Varying: Sized
+pub trait Varying: Sized
 {
-    /// interpolate self using barycentric coordinates
+    /// interpolate self using barycentric coordinates. this is a plain
+    /// affine blend in screen space, correct for quantities like depth;
+    /// see [Varying::interpolate_persp] for attributes that need to be
+    /// perspective-corrected
     fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self;
+
+    /// scale every component by a scalar. used to pre-divide a varying
+    /// by its vertex's `w` (and to divide the result back out) when
+    /// interpolating perspective-correctly
+    fn scale(self, s: f32) -> Self;
+
+    /// interpolate self with perspective correction, given each vertex's
+    /// `1/w`. the attribute is blended as `attr/w`, divided by the
+    /// blended `1/w`, recovering the true value:
+    /// `attr = sum(bar_i * attr_i / w_i) / sum(bar_i / w_i)`
+    fn interpolate_persp(tri: Vec3<Self>, inv_w: Vec3<f32>, bar: Vec3<f32>) -> Self
+    {
+        // attribute-over-w at each vertex
+        let over_w = Vec3::new(
+            tri.x.scale(inv_w.x),
+            tri.y.scale(inv_w.y),
+            tri.z.scale(inv_w.z),
+        );
+        // blended 1/w
+        let den = f32::interpolate(inv_w, bar);
+
+        Self::interpolate(over_w, bar).scale(1.0 / den)
+    }
+}
+
+/// a shader's `Varyings` are carried as tuples of [Varying]s, so the
+/// tuple itself is interpolated component-wise
+impl<A: Varying> Varying for (A,)
+{
+    fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self
+    {
+        (A::interpolate(Vec3::new(tri.x.0, tri.y.0, tri.z.0), bar),)
+    }
+
+    fn scale(self, s: f32) -> Self
+    {
+        (self.0.scale(s),)
+    }
+}
+
+impl<A: Varying, B: Varying> Varying for (A, B)
+{
+    fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self
+    {
+        (
+            A::interpolate(Vec3::new(tri.x.0, tri.y.0, tri.z.0), bar),
+            B::interpolate(Vec3::new(tri.x.1, tri.y.1, tri.z.1), bar),
+        )
+    }
+
+    fn scale(self, s: f32) -> Self
+    {
+        (self.0.scale(s), self.1.scale(s))
+    }
+}
+
+impl<A: Varying, B: Varying, C: Varying> Varying for (A, B, C)
+{
+    fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self
+    {
+        (
+            A::interpolate(Vec3::new(tri.x.0, tri.y.0, tri.z.0), bar),
+            B::interpolate(Vec3::new(tri.x.1, tri.y.1, tri.z.1), bar),
+            C::interpolate(Vec3::new(tri.x.2, tri.y.2, tri.z.2), bar),
+        )
+    }
+
+    fn scale(self, s: f32) -> Self
+    {
+        (self.0.scale(s), self.1.scale(s), self.2.scale(s))
+    }
 }
 
 impl Varying for f32
@@ -63,6 +203,44 @@ impl Varying for f32
     {
         tri.dot(bar)
     }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self * s
+    }
+}
+
+impl Varying for Vec2<f32>
+{
+    fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self
+    {
+        let x = Vec3::new(tri.x.x, tri.y.x, tri.z.x).dot(bar);
+        let y = Vec3::new(tri.x.y, tri.y.y, tri.z.y).dot(bar);
+
+        Vec2::new(x, y)
+    }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self * s
+    }
+}
+
+impl Varying for Vec3<f32>
+{
+    fn interpolate(tri: Vec3<Self>, bar: Vec3<f32>) -> Self
+    {
+        let x = Vec3::new(tri.x.x, tri.y.x, tri.z.x).dot(bar);
+        let y = Vec3::new(tri.x.y, tri.y.y, tri.z.y).dot(bar);
+        let z = Vec3::new(tri.x.z, tri.y.z, tri.z.z).dot(bar);
+
+        Vec3::new(x, y, z)
+    }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self * s
+    }
 }
 
 impl Varying for Rgba<f32>
@@ -76,6 +254,11 @@ impl Varying for Rgba<f32>
 
         Rgba::new(r, g, b, a)
     }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self.map(|n| n * s)
+    }
 }
 
 impl Varying for Rgba<u8>
@@ -84,6 +267,11 @@ impl Varying for Rgba<u8>
     {
         Rgba::<f32>::interpolate(tri.map(|n| n.as_()), bar).as_()
     }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self.map(|n| ((n as f32) * s) as u8)
+    }
 }
 
 impl Varying for Rgb<f32>
@@ -96,6 +284,11 @@ impl Varying for Rgb<f32>
 
         Rgb::new(r, g, b)
     }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self.map(|n| n * s)
+    }
 }
 
 impl Varying for Rgb<u8>
@@ -104,4 +297,9 @@ impl Varying for Rgb<u8>
     {
         Rgb::<f32>::interpolate(tri.map(|n| n.as_()), bar).as_()
     }
+
+    fn scale(self, s: f32) -> Self
+    {
+        self.map(|n| ((n as f32) * s) as u8)
+    }
 }
\ No newline at end of file